@@ -2,13 +2,15 @@
 Represents a token which can be used by the parser
 # Examples
 ```
-"1" -> Token::Number(1)
+"1" -> Token::Number(1.0)
 "+" -> Token::Plus()
-"1+2" -> [Token::Number(1), Token::Plus(), Token::Number(2)]
+"1+2" -> [Token::Number(1.0), Token::Plus(), Token::Number(2.0)]
 ```
+Identifiers borrow their text directly from the scanned input, so `Token`
+carries the lifetime of the source string.
  */
-#[derive(Eq, PartialEq, Debug)]
-pub enum Token {
+#[derive(PartialEq, Debug)]
+pub enum Token<'a> {
     /** ( */
     LParen(),
     /** ) */
@@ -22,29 +24,79 @@ pub enum Token {
     /** / */
     Divide(),
 
-    /** 0 - 9 */
-    Number(i8),
+    /** A numeric literal such as `2`, `42` or `2.3` */
+    Number(f64),
     /** . */
     Point(),
 
+    /** A name such as a variable, e.g. `x`, borrowed from the input */
+    Ident(&'a str),
+    /** The `let` keyword */
+    Let,
+    /** = */
+    Assign(),
+
     /** \/\/ */
     Comment(),
-    /** Empty line */
-    None(),
-    /** Unknown token */
-    NaN(),
     /** Whitespace  */
     Whitespace(),
+    /** End of input */
+    Eof,
+}
+
+/**
+An error raised by the [`Lexer`] when it cannot turn the input into tokens.
+
+Errors render as `line:col: <reason>`, e.g. `1:3: unexpected character '@'`.
+ */
+#[derive(PartialEq, Debug)]
+pub enum LexError {
+    /** A character that does not start any known token */
+    UnexpectedChar { ch: char, line: u32, col: u32 },
+    /** A numeric run that failed to parse, e.g. `3.` or `1.2.3` */
+    MalformedNumber { text: String, span: Span },
+}
+
+impl std::fmt::Display for LexError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LexError::UnexpectedChar { ch, line, col } => {
+                write!(f, "{}:{}: unexpected character '{}'", line, col, ch)
+            }
+            LexError::MalformedNumber { text, span } => {
+                write!(f, "{}:{}: malformed number '{}'", span.line, span.start_col, text)
+            }
+        }
+    }
 }
 
+impl std::error::Error for LexError {}
+
 /**
-Represents a lexer that can scan lines and create a stream of tokens
+Represents the position of a token within the source, used for diagnostics.
+
+`line` and the columns are 1-based; `end_col` is exclusive, i.e. it points at
+the column just past the last character of the token.
  */
-pub struct Lexer {
-    lines: Vec<String>,
-    line: i8,
-    count: i8,
-    token: i8,
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct Span {
+    pub line: u32,
+    pub start_col: u32,
+    pub end_col: u32,
+}
+
+/**
+A streaming lexer that borrows its input and hands out one token at a time.
+
+It keeps a byte `position` into `input` plus a 1-based `line`/`col` cursor, so
+no line is ever copied and identifier slices point straight back into the
+source.
+ */
+pub struct Lexer<'a> {
+    input: &'a str,
+    position: usize,
+    line: u32,
+    col: u32,
 }
 
 /**
@@ -52,103 +104,278 @@ Default implementation of the lexer
 
 # Example
 ```
-let mut lexer: Lexer = Lexer::new(vec![String::from("1+2")]);
-let result: Vec<Token> = lexer.next_line();
-println!("{:?}"); // [Token::Number(1), Token::Plus(), Token::Number(2)]
+let mut lexer = Lexer::new("1+2");
+let (token, _span) = lexer.next_token().unwrap(); // (Token::Number(1.0), ..)
 ```
  */
-impl Lexer {
-    pub fn new(lines: Vec<String>) -> Self {
+impl<'a> Lexer<'a> {
+    pub fn new(input: &'a str) -> Self {
         return Lexer {
-            lines,
-            line: 0,
-            count: 0,
-            token: 0,
+            input,
+            position: 0,
+            line: 1,
+            col: 1,
         };
     }
 
-    pub fn next_line(&mut self) -> Vec<Token> {
-        let mut tokens: Vec<Token> = vec![];
-        self.line += 1;
+    /** The character at the cursor, or `None` at end of input. */
+    fn current(&self) -> Option<char> {
+        return self.input[self.position..].chars().next();
+    }
 
-        let mut line = String::new();
-        let mut count = 0;
-        for entry in self.lines.as_slice() {
-            if count == self.line - 1 {
-                line = entry.clone();
-                break;
-            }
-            count += 1;
-        }
+    /** The character one step past the cursor, used for `//` lookahead. */
+    fn peek_second(&self) -> Option<char> {
+        let mut chars = self.input[self.position..].chars();
+        chars.next();
+        return chars.next();
+    }
 
-        if line.is_empty() {
-            return vec![Token::None()];
+    /** Advance the cursor past `c`, updating the line/column bookkeeping. */
+    fn bump(&mut self, c: char) {
+        self.position += c.len_utf8();
+        if c == '\n' {
+            self.line += 1;
+            self.col = 1;
+        } else {
+            self.col += 1;
         }
+    }
 
-        if line.starts_with("//") {
-            return vec![Token::Comment()];
-        }
+    /**
+    Scan and return the next token together with its span, or [`Token::Eof`]
+    once the input is exhausted.
+    */
+    pub fn next_token(&mut self) -> Result<(Token<'a>, Span), LexError> {
+        let line = self.line;
+        let start_col = self.col;
+
+        let c = match self.current() {
+            Some(c) => c,
+            None => {
+                return Ok((Token::Eof, Span { line, start_col, end_col: start_col }));
+            }
+        };
 
-        for c in line.chars() {
-            match c {
-                '(' => tokens.push(Token::LParen()),
-                ')' => tokens.push(Token::RParen()),
-                '+' => tokens.push(Token::Plus()),
-                '-' => tokens.push(Token::Minus()),
-                '*' => tokens.push(Token::Times()),
-                '/' => tokens.push(Token::Divide()),
-
-                '0' => tokens.push(Token::Number(0)),
-                '1' => tokens.push(Token::Number(1)),
-                '2' => tokens.push(Token::Number(2)),
-                '3' => tokens.push(Token::Number(3)),
-                '4' => tokens.push(Token::Number(4)),
-                '5' => tokens.push(Token::Number(5)),
-                '6' => tokens.push(Token::Number(6)),
-                '7' => tokens.push(Token::Number(7)),
-                '8' => tokens.push(Token::Number(8)),
-                '9' => tokens.push(Token::Number(9)),
-
-                '.' => tokens.push(Token::Point()),
-                ' ' => tokens.push(Token::Whitespace()),
-                _ => tokens.push(Token::NaN())
+        let token = match c {
+            '(' => { self.bump(c); Token::LParen() }
+            ')' => { self.bump(c); Token::RParen() }
+            '+' => { self.bump(c); Token::Plus() }
+            '-' => { self.bump(c); Token::Minus() }
+            '*' => { self.bump(c); Token::Times() }
+            '/' => {
+                // `//` opens a comment running to the end of the line.
+                if self.peek_second() == Some('/') {
+                    while let Some(next) = self.current() {
+                        if next == '\n' {
+                            break;
+                        }
+                        self.bump(next);
+                    }
+                    Token::Comment()
+                } else {
+                    self.bump(c);
+                    Token::Divide()
+                }
             }
+
+            '0'..='9' => {
+                // Greedily accumulate a contiguous numeric run: all following
+                // digits plus at most one `.`, then parse the borrowed slice.
+                let start = self.position;
+                let mut seen_point = false;
+                self.bump(c);
+                while let Some(next) = self.current() {
+                    match next {
+                        '0'..='9' => self.bump(next),
+                        '.' if !seen_point => {
+                            seen_point = true;
+                            self.bump(next);
+                        }
+                        _ => break,
+                    }
+                }
+                // A trailing `.` (`3.`) or a second `.` immediately following
+                // the run (`1.2.3`) must surface as an error rather than being
+                // silently accepted or split off as a `Point`. `parse::<f64>()`
+                // happily accepts `3.`, so reject these runs explicitly.
+                let malformed =
+                    self.input[start..self.position].ends_with('.') || self.current() == Some('.');
+                if malformed {
+                    if self.current() == Some('.') {
+                        self.bump('.');
+                    }
+                    let text = &self.input[start..self.position];
+                    let span = Span { line, start_col, end_col: self.col };
+                    return Err(LexError::MalformedNumber { text: text.to_string(), span });
+                }
+                let text = &self.input[start..self.position];
+                match text.parse::<f64>() {
+                    Ok(value) => Token::Number(value),
+                    Err(_) => {
+                        let span = Span { line, start_col, end_col: self.col };
+                        return Err(LexError::MalformedNumber { text: text.to_string(), span });
+                    }
+                }
+            }
+
+            'a'..='z' | 'A'..='Z' | '_' => {
+                // Borrow a run of alphabetic/`_` characters, then decide whether
+                // it is a keyword or a plain identifier.
+                let start = self.position;
+                self.bump(c);
+                while let Some(next) = self.current() {
+                    if next.is_ascii_alphabetic() || next == '_' {
+                        self.bump(next);
+                    } else {
+                        break;
+                    }
+                }
+                let text = &self.input[start..self.position];
+                match text {
+                    "let" => Token::Let,
+                    _ => Token::Ident(text),
+                }
+            }
+
+            '=' => { self.bump(c); Token::Assign() }
+            '.' => { self.bump(c); Token::Point() }
+            _ if c.is_whitespace() => { self.bump(c); Token::Whitespace() }
+            _ => {
+                return Err(LexError::UnexpectedChar { ch: c, line, col: start_col });
+            }
+        };
+
+        return Ok((token, Span { line, start_col, end_col: self.col }));
+    }
+}
+
+impl<'a> Iterator for Lexer<'a> {
+    type Item = (Token<'a>, Span);
+
+    fn next(&mut self) -> Option<(Token<'a>, Span)> {
+        match self.next_token() {
+            Ok((Token::Eof, _)) => None,
+            Ok(pair) => Some(pair),
+            Err(_) => None,
         }
+    }
+}
 
-        return tokens;
+/**
+Scan `input` into a vector of tokens, terminated by a [`Token::Eof`].
+
+A thin convenience wrapper for call sites that want the whole stream at once.
+ */
+pub fn tokenize(input: &str) -> Result<Vec<(Token<'_>, Span)>, LexError> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens = vec![];
+    loop {
+        let (token, span) = lexer.next_token()?;
+        let eof = token == Token::Eof;
+        tokens.push((token, span));
+        if eof {
+            break;
+        }
     }
+    return Ok(tokens);
 }
 
 #[cfg(test)]
 pub mod tests {
-    use crate::lang::lexer::{Lexer, Token};
+    use crate::lang::lexer::{tokenize, LexError, Span, Token};
+
+    fn span(line: u32, start_col: u32, end_col: u32) -> Span {
+        Span { line, start_col, end_col }
+    }
 
     #[test]
     fn test_lexer_with_comment() {
-        let mut lexer = Lexer::new(vec![String::from("// 1")]);
-        let mut result = vec![Token::Comment()];
-
-        assert_eq!(lexer.next_line(), result);
-        result.push(Token::Number(1));
-        assert_ne!(lexer.next_line(), result);
+        assert_eq!(
+            tokenize("// 1"),
+            Ok(vec![
+                (Token::Comment(), span(1, 1, 5)),
+                (Token::Eof, span(1, 5, 5)),
+            ])
+        );
     }
 
     #[test]
     fn test_basic_lexer() {
-        let mut lexer = Lexer::new(vec![String::from("(+-) */.")]);
-        let result = vec![Token::LParen(), Token::Plus(), Token::Minus(),
-                          Token::RParen(), Token::Whitespace(), Token::Times(), Token::Divide(), Token::Point()];
-
-        assert_eq!(lexer.next_line(), result);
+        assert_eq!(
+            tokenize("(+-) */."),
+            Ok(vec![
+                (Token::LParen(), span(1, 1, 2)),
+                (Token::Plus(), span(1, 2, 3)),
+                (Token::Minus(), span(1, 3, 4)),
+                (Token::RParen(), span(1, 4, 5)),
+                (Token::Whitespace(), span(1, 5, 6)),
+                (Token::Times(), span(1, 6, 7)),
+                (Token::Divide(), span(1, 7, 8)),
+                (Token::Point(), span(1, 8, 9)),
+                (Token::Eof, span(1, 9, 9)),
+            ])
+        );
     }
 
     #[test]
     fn test_lexer_with_number() {
-        let mut lexer = Lexer::new(vec![String::from("1*(-2.3+2)")]);
-        let result = vec![Token::Number(1), Token::Times(), Token::LParen(),
-                          Token::Minus(), Token::Number(2), Token::Point(), Token::Number(3),
-                          Token::Plus(), Token::Number(2), Token::RParen()];
+        assert_eq!(
+            tokenize("1*(-2.3+2)"),
+            Ok(vec![
+                (Token::Number(1.0), span(1, 1, 2)),
+                (Token::Times(), span(1, 2, 3)),
+                (Token::LParen(), span(1, 3, 4)),
+                (Token::Minus(), span(1, 4, 5)),
+                (Token::Number(2.3), span(1, 5, 8)),
+                (Token::Plus(), span(1, 8, 9)),
+                (Token::Number(2.0), span(1, 9, 10)),
+                (Token::RParen(), span(1, 10, 11)),
+                (Token::Eof, span(1, 11, 11)),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_lexer_with_let_binding() {
+        assert_eq!(
+            tokenize("let x = 1"),
+            Ok(vec![
+                (Token::Let, span(1, 1, 4)),
+                (Token::Whitespace(), span(1, 4, 5)),
+                (Token::Ident("x"), span(1, 5, 6)),
+                (Token::Whitespace(), span(1, 6, 7)),
+                (Token::Assign(), span(1, 7, 8)),
+                (Token::Whitespace(), span(1, 8, 9)),
+                (Token::Number(1.0), span(1, 9, 10)),
+                (Token::Eof, span(1, 10, 10)),
+            ])
+        );
+    }
 
-        assert_eq!(lexer.next_line(), result);
+    #[test]
+    fn test_malformed_number_errors() {
+        assert_eq!(
+            tokenize("3."),
+            Err(LexError::MalformedNumber { text: "3.".to_string(), span: span(1, 1, 3) })
+        );
+        assert_eq!(
+            tokenize("1.2.3"),
+            Err(LexError::MalformedNumber { text: "1.2.".to_string(), span: span(1, 1, 5) })
+        );
+    }
+
+    #[test]
+    fn test_unexpected_char_errors() {
+        assert_eq!(
+            tokenize("1 @ 2"),
+            Err(LexError::UnexpectedChar { ch: '@', line: 1, col: 3 })
+        );
+    }
+
+    #[test]
+    fn test_iterator_skips_eof() {
+        let lexer = crate::lang::lexer::Lexer::new("1+2");
+        let tokens: Vec<Token> = lexer.map(|(token, _)| token).collect();
+        assert_eq!(tokens, vec![Token::Number(1.0), Token::Plus(), Token::Number(2.0)]);
     }
 }
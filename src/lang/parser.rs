@@ -0,0 +1,224 @@
+use crate::lang::lexer::{Span, Token};
+
+/**
+The abstract syntax tree produced from a stream of [`Token`]s.
+
+An expression is either a literal number, a unary negation, or a binary
+operation over two sub-expressions.
+ */
+#[derive(PartialEq, Debug)]
+pub enum Expr {
+    /** A literal value, e.g. `2.3` */
+    Number(f64),
+    /** A prefix operation such as `-x` */
+    Unary { op: Op, expr: Box<Expr> },
+    /** A binary operation such as `1 + 2` */
+    Binary { op: Op, lhs: Box<Expr>, rhs: Box<Expr> },
+}
+
+/**
+The arithmetic operators understood by the parser.
+ */
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+/**
+Errors that can arise while parsing or evaluating an expression.
+ */
+#[derive(PartialEq, Debug)]
+pub enum ParseError {
+    /** A token appeared where an atom or operator was expected */
+    UnexpectedToken,
+    /** The input ended before the expression was complete */
+    UnexpectedEof,
+    /** A `(` was never closed or a `)` had no matching `(` */
+    UnmatchedParen,
+    /** The right-hand side of a `/` evaluated to zero */
+    DivisionByZero,
+}
+
+impl Expr {
+    /**
+    Fold the tree down to a single value, surfacing division by zero.
+    */
+    pub fn eval(&self) -> Result<f64, ParseError> {
+        match self {
+            Expr::Number(value) => Ok(*value),
+            Expr::Unary { op, expr } => {
+                let value = expr.eval()?;
+                match op {
+                    Op::Sub => Ok(-value),
+                    _ => Err(ParseError::UnexpectedToken),
+                }
+            }
+            Expr::Binary { op, lhs, rhs } => {
+                let lhs = lhs.eval()?;
+                let rhs = rhs.eval()?;
+                match op {
+                    Op::Add => Ok(lhs + rhs),
+                    Op::Sub => Ok(lhs - rhs),
+                    Op::Mul => Ok(lhs * rhs),
+                    Op::Div => {
+                        if rhs == 0.0 {
+                            Err(ParseError::DivisionByZero)
+                        } else {
+                            Ok(lhs / rhs)
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/**
+A Pratt parser over a flat token stream.
+
+Whitespace and `Eof` tokens are dropped. Any other token that is not a number,
+an arithmetic operator or a parenthesis is reported as
+[`ParseError::UnexpectedToken`].
+ */
+pub struct Parser<'a> {
+    tokens: Vec<Token<'a>>,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(tokens: Vec<Token<'a>>) -> Self {
+        let tokens = tokens
+            .into_iter()
+            .filter(|t| !matches!(t, Token::Whitespace() | Token::Eof))
+            .collect();
+        return Parser { tokens, pos: 0 };
+    }
+
+    /**
+    Build a parser straight from a lexed `(Token, Span)` stream, such as the
+    output of [`tokenize`](crate::lang::lexer::tokenize) or the
+    [`Lexer`](crate::lang::lexer::Lexer) iterator. Spans are dropped; only the tokens feed the grammar.
+    */
+    pub fn from_tokens(tokens: impl Iterator<Item = (Token<'a>, Span)>) -> Self {
+        return Parser::new(tokens.map(|(token, _)| token).collect());
+    }
+
+    /**
+    Parse the whole token stream into a single expression, erroring if any
+    tokens are left over once the top-level expression has been consumed.
+    */
+    pub fn parse(&mut self) -> Result<Expr, ParseError> {
+        let expr = self.parse_expr(0)?;
+        match self.peek() {
+            None => return Ok(expr),
+            Some(Token::RParen()) => return Err(ParseError::UnmatchedParen),
+            Some(_) => return Err(ParseError::UnexpectedToken),
+        }
+    }
+
+    fn peek(&self) -> Option<&Token<'a>> {
+        return self.tokens.get(self.pos);
+    }
+
+    fn next(&mut self) -> Option<&Token<'a>> {
+        let token = self.tokens.get(self.pos);
+        self.pos += 1;
+        return token;
+    }
+
+    // Prefix operators bind tighter than any binary operator so that `-2 * 3`
+    // groups as `(-2) * 3`.
+    const PREFIX_BP: u8 = 5;
+
+    fn parse_expr(&mut self, min_bp: u8) -> Result<Expr, ParseError> {
+        let mut lhs = match self.next() {
+            Some(Token::Number(value)) => Expr::Number(*value),
+            Some(Token::Minus()) => {
+                let expr = self.parse_expr(Self::PREFIX_BP)?;
+                Expr::Unary { op: Op::Sub, expr: Box::new(expr) }
+            }
+            Some(Token::LParen()) => {
+                let expr = self.parse_expr(0)?;
+                match self.next() {
+                    Some(Token::RParen()) => expr,
+                    _ => return Err(ParseError::UnmatchedParen),
+                }
+            }
+            Some(Token::RParen()) => return Err(ParseError::UnmatchedParen),
+            Some(_) => return Err(ParseError::UnexpectedToken),
+            None => return Err(ParseError::UnexpectedEof),
+        };
+
+        loop {
+            let (op, l_bp, r_bp) = match self.peek() {
+                Some(Token::Plus()) => (Op::Add, 1, 2),
+                Some(Token::Minus()) => (Op::Sub, 1, 2),
+                Some(Token::Times()) => (Op::Mul, 3, 4),
+                Some(Token::Divide()) => (Op::Div, 3, 4),
+                _ => break,
+            };
+
+            if l_bp < min_bp {
+                break;
+            }
+
+            self.next();
+            let rhs = self.parse_expr(r_bp)?;
+            lhs = Expr::Binary { op, lhs: Box::new(lhs), rhs: Box::new(rhs) };
+        }
+
+        return Ok(lhs);
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use crate::lang::lexer::{tokenize, Token};
+    use crate::lang::parser::{Parser, ParseError};
+
+    #[test]
+    fn test_parse_from_lexed_source() {
+        // 1 + 2 * 3 == 7, straight from lexed source rather than hand-built tokens.
+        let tokens = tokenize("1 + 2 * 3").unwrap();
+        let expr = Parser::from_tokens(tokens.into_iter()).parse().unwrap();
+        assert_eq!(expr.eval(), Ok(7.0));
+    }
+
+    #[test]
+    fn test_precedence() {
+        // 1 + 2 * 3 == 7
+        let tokens = vec![
+            Token::Number(1.0), Token::Plus(), Token::Number(2.0),
+            Token::Times(), Token::Number(3.0),
+        ];
+        let expr = Parser::new(tokens).parse().unwrap();
+        assert_eq!(expr.eval(), Ok(7.0));
+    }
+
+    #[test]
+    fn test_parens_and_unary() {
+        // -(1 + 2) * 2 == -6
+        let tokens = vec![
+            Token::Minus(), Token::LParen(), Token::Number(1.0), Token::Plus(),
+            Token::Number(2.0), Token::RParen(), Token::Times(), Token::Number(2.0),
+        ];
+        let expr = Parser::new(tokens).parse().unwrap();
+        assert_eq!(expr.eval(), Ok(-6.0));
+    }
+
+    #[test]
+    fn test_division_by_zero() {
+        let tokens = vec![Token::Number(1.0), Token::Divide(), Token::Number(0.0)];
+        let expr = Parser::new(tokens).parse().unwrap();
+        assert_eq!(expr.eval(), Err(ParseError::DivisionByZero));
+    }
+
+    #[test]
+    fn test_unmatched_paren() {
+        let tokens = vec![Token::LParen(), Token::Number(1.0)];
+        assert_eq!(Parser::new(tokens).parse(), Err(ParseError::UnmatchedParen));
+    }
+}